@@ -0,0 +1,315 @@
+//! Defines an optional S-expression-style filter language for search and listing endpoints,
+//! parsed from a single reserved `Request` query string parameter (conventionally `filter`),
+//! e.g. `filter=(and (eq status open) (gt age 18))`.
+//!
+//! This sits alongside `FromQueryString` rather than replacing it: a handler reads the raw
+//! value via the usual `QueryStringMapping`/`QueryStringExtractor` machinery, then passes the
+//! decoded string to `FilterExpr::parse`.
+
+use http::query_string::FromQueryStringError;
+
+/// A parsed filter expression, as produced by `FilterExpr::parse`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    /// All of the nested expressions must hold.
+    And(Vec<FilterExpr>),
+    /// At least one of the nested expressions must hold.
+    Or(Vec<FilterExpr>),
+    /// The nested expression must not hold.
+    Not(Box<FilterExpr>),
+    /// A single `field op value` comparison.
+    Cmp {
+        /// The field the comparison applies to.
+        field: String,
+        /// The comparison operator.
+        op: FilterOp,
+        /// The literal compared against `field`.
+        value: FilterValue,
+    },
+}
+
+/// A comparison operator usable within a `Cmp` node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `eq` - equal to.
+    Eq,
+    /// `ne` - not equal to.
+    Ne,
+    /// `lt` - less than.
+    Lt,
+    /// `le` - less than or equal to.
+    Le,
+    /// `gt` - greater than.
+    Gt,
+    /// `ge` - greater than or equal to.
+    Ge,
+    /// `contains` - substring/membership test.
+    Contains,
+}
+
+impl FilterOp {
+    fn from_token(token: &str) -> Option<FilterOp> {
+        match token {
+            "eq" => Some(FilterOp::Eq),
+            "ne" => Some(FilterOp::Ne),
+            "lt" => Some(FilterOp::Lt),
+            "le" => Some(FilterOp::Le),
+            "gt" => Some(FilterOp::Gt),
+            "ge" => Some(FilterOp::Ge),
+            "contains" => Some(FilterOp::Contains),
+            _ => None,
+        }
+    }
+}
+
+/// A literal value compared against a field within a `Cmp` node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    /// A string literal, either bare (`open`) or quoted (`"open now"`).
+    Str(String),
+    /// A numeric literal (`18`, `-4.5`).
+    Num(f64),
+}
+
+impl FilterValue {
+    /// Converts a `Token::Bare` atom to a `Num` if it parses as one, falling back to `Str`
+    /// otherwise. A `Token::Quoted` atom is always a `Str`, even if it looks numeric, so that
+    /// e.g. a zip code or account number can be expressed as `"90210"` without being parsed as a
+    /// number.
+    fn from_token(token: &Token) -> FilterValue {
+        match *token {
+            Token::Quoted(ref atom) => FilterValue::Str(atom.clone()),
+            Token::Bare(ref atom) => {
+                match atom.parse::<f64>() {
+                    Ok(n) => FilterValue::Num(n),
+                    Err(_) => FilterValue::Str(atom.clone()),
+                }
+            }
+            Token::Open | Token::Close => unreachable!("from_token is only called on atoms"),
+        }
+    }
+}
+
+impl FilterExpr {
+    /// Parses a filter expression from its S-expression text, e.g.
+    /// `(and (eq status open) (gt age 18))`.
+    ///
+    /// Rejects unbalanced parentheses, unknown operators and empty `and`/`or` groups with a
+    /// descriptive `FromQueryStringError`.
+    ///
+    /// #Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// #
+    /// # use gotham::http::filter::{FilterExpr, FilterOp, FilterValue};
+    /// #
+    /// # pub fn main() {
+    ///       let expr = FilterExpr::parse("(eq status open)").unwrap();
+    ///       assert_eq!(expr,
+    ///                  FilterExpr::Cmp {
+    ///                      field: "status".to_string(),
+    ///                      op: FilterOp::Eq,
+    ///                      value: FilterValue::Str("open".to_string()),
+    ///                  });
+    ///
+    ///       assert!(FilterExpr::parse("(and)").is_err());
+    ///       assert!(FilterExpr::parse("(eq status open").is_err());
+    ///
+    ///       // A quoted literal is always a string, even if it looks numeric, so that values
+    ///       // like zip codes aren't misparsed as numbers.
+    ///       let expr = FilterExpr::parse("(eq zip \"90210\")").unwrap();
+    ///       assert_eq!(expr,
+    ///                  FilterExpr::Cmp {
+    ///                      field: "zip".to_string(),
+    ///                      op: FilterOp::Eq,
+    ///                      value: FilterValue::Str("90210".to_string()),
+    ///                  });
+    ///       let expr = FilterExpr::parse("(eq zip 90210)").unwrap();
+    ///       assert_eq!(expr,
+    ///                  FilterExpr::Cmp {
+    ///                      field: "zip".to_string(),
+    ///                      op: FilterOp::Eq,
+    ///                      value: FilterValue::Num(90210.0),
+    ///                  });
+    ///
+    ///       // Deeply nested expressions are rejected rather than overflowing the stack.
+    ///       let deeply_nested = "(not ".repeat(100) + "(eq a b)" + &")".repeat(100);
+    ///       assert!(FilterExpr::parse(&deeply_nested).is_err());
+    /// # }
+    /// ```
+    pub fn parse(input: &str) -> Result<FilterExpr, FromQueryStringError> {
+        let tokens = tokenize(input)?;
+        let mut pos = 0;
+
+        let expr = parse_expr(&tokens, &mut pos, 0)?;
+        if pos != tokens.len() {
+            return Err(FromQueryStringError::new("unexpected input after filter expression"));
+        }
+
+        Ok(expr)
+    }
+}
+
+/// A single token produced by `tokenize`.
+///
+/// Keeps the quoted/bare distinction that the raw text draws so that `FilterValue::from_token`
+/// can tell a quoted numeric-looking literal (`"90210"`) apart from a bare one (`90210`).
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `(`
+    Open,
+    /// `)`
+    Close,
+    /// An atom that was not quoted, e.g. `eq`, `status`, `18`.
+    Bare(String),
+    /// An atom that appeared within `"..."`, e.g. the `open now` in `"open now"`.
+    Quoted(String),
+}
+
+impl Token {
+    /// Returns the atom text of a `Bare` or `Quoted` token, ignoring quoting, for comparisons
+    /// against keywords like `"and"` or operator names where either form is accepted.
+    fn atom(&self) -> Option<&str> {
+        match *self {
+            Token::Bare(ref atom) | Token::Quoted(ref atom) => Some(atom),
+            Token::Open | Token::Close => None,
+        }
+    }
+}
+
+/// Splits filter text into `(`, `)` and atom tokens, treating `"..."` as a single quoted atom.
+fn tokenize(input: &str) -> Result<Vec<Token>, FromQueryStringError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(idx, ch)) = chars.peek() {
+        match ch {
+            '(' => {
+                tokens.push(Token::Open);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                chars.next();
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let start = idx + 1;
+                let mut end = None;
+                while let Some(&(i, c)) = chars.peek() {
+                    chars.next();
+                    if c == '"' {
+                        end = Some(i);
+                        break;
+                    }
+                }
+                match end {
+                    Some(end) => tokens.push(Token::Quoted(input[start..end].to_string())),
+                    None => return Err(FromQueryStringError::new("unterminated string literal in filter expression")),
+                }
+            }
+            _ => {
+                let start = idx;
+                let mut end = input.len();
+                while let Some(&(i, c)) = chars.peek() {
+                    if c == '(' || c == ')' || c.is_whitespace() {
+                        end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                tokens.push(Token::Bare(input[start..end].to_string()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// The maximum nesting depth `parse_expr` will recurse to. Chosen comfortably below where a
+/// deeply nested `and`/`or`/`not` expression would risk overflowing even a small worker-thread
+/// stack; a legitimate filter has no reason to nest this deeply.
+const MAX_FILTER_DEPTH: usize = 64;
+
+/// Parses a single `(op ...)` expression starting at `tokens[*pos]`, advancing `*pos` past its
+/// closing `)`.
+fn parse_expr(tokens: &[Token], pos: &mut usize, depth: usize) -> Result<FilterExpr, FromQueryStringError> {
+    if depth > MAX_FILTER_DEPTH {
+        return Err(FromQueryStringError::new(format!("filter expression nested too deeply (max depth {})",
+                                                       MAX_FILTER_DEPTH)));
+    }
+
+    match tokens.get(*pos) {
+        Some(&Token::Open) => *pos += 1,
+        Some(_) => return Err(FromQueryStringError::new("expected '(' to start a filter expression")),
+        None => return Err(FromQueryStringError::new("unexpected end of filter expression")),
+    }
+
+    let op = match tokens.get(*pos).and_then(Token::atom) {
+        Some(op) => op.to_string(),
+        None => return Err(FromQueryStringError::new("expected an operator after '('")),
+    };
+    *pos += 1;
+
+    let expr = match op.as_str() {
+        "and" | "or" => {
+            let mut children = Vec::new();
+            loop {
+                match tokens.get(*pos) {
+                    Some(&Token::Close) => break,
+                    Some(&Token::Open) => children.push(parse_expr(tokens, pos, depth + 1)?),
+                    Some(_) => return Err(FromQueryStringError::new(format!("expected a nested expression inside '{}'", op))),
+                    None => return Err(FromQueryStringError::new("unbalanced parentheses in filter expression")),
+                }
+            }
+
+            if children.is_empty() {
+                return Err(FromQueryStringError::new(format!("'{}' requires at least one nested expression", op)));
+            }
+
+            if op == "and" {
+                FilterExpr::And(children)
+            } else {
+                FilterExpr::Or(children)
+            }
+        }
+        "not" => {
+            match tokens.get(*pos) {
+                Some(&Token::Close) => return Err(FromQueryStringError::new("'not' requires a nested expression")),
+                _ => FilterExpr::Not(Box::new(parse_expr(tokens, pos, depth + 1)?)),
+            }
+        }
+        op if FilterOp::from_token(op).is_some() => {
+            let field = match tokens.get(*pos).and_then(Token::atom) {
+                Some(field) => field.to_string(),
+                None => return Err(FromQueryStringError::new(format!("expected a field name after '{}'", op))),
+            };
+            *pos += 1;
+
+            let value = match tokens.get(*pos) {
+                Some(value) if value.atom().is_some() => FilterValue::from_token(value),
+                _ => return Err(FromQueryStringError::new(format!("expected a value after '{} {}'", op, field))),
+            };
+            *pos += 1;
+
+            FilterExpr::Cmp {
+                field,
+                op: FilterOp::from_token(op).unwrap(),
+                value,
+            }
+        }
+        other => return Err(FromQueryStringError::new(format!("unknown filter operator '{}'", other))),
+    };
+
+    match tokens.get(*pos) {
+        Some(&Token::Close) => {
+            *pos += 1;
+            Ok(expr)
+        }
+        _ => Err(FromQueryStringError::new("unbalanced parentheses in filter expression")),
+    }
+}
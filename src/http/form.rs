@@ -0,0 +1,107 @@
+//! Defines functionality for operating on `application/x-www-form-urlencoded` `Request` bodies
+
+use std::str;
+use std::error::Error;
+
+use state::State;
+use http::FormUrlDecoded;
+use http::query_string::{split, QueryStringMapping, FromQueryString, FromQueryStringError};
+
+/// The MIME type a `Request` body must declare via its `Content-Type` header to be accepted by
+/// `FormExtractor`.
+pub const FORM_URLENCODED_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Derived through the macro of the same name supplied by `gotham-derive` for application
+/// defined structs that will pass an `application/x-www-form-urlencoded` `Request` body to
+/// custom `Middleware` and `Handler` implementations.
+///
+/// Shares its field level conversion logic with `QueryStringExtractor`, so a single derive
+/// invocation can implement both traits for the same struct.
+pub trait FormExtractor {
+    /// Populates the struct with data decoded from an `application/x-www-form-urlencoded`
+    /// `Request` body and adds it to `State`.
+    ///
+    /// `content_type` is the value of the `Request`'s `Content-Type` header, if any, and `body`
+    /// is the already read `Request` body.
+    fn extract(state: &mut State, content_type: Option<&str>, body: &[u8]) -> Result<(), String>;
+}
+
+/// Converts a key=value pair decoded from an `application/x-www-form-urlencoded` `Request` body
+/// into a type safe value.
+///
+/// Reuses `FromQueryString` so that a single field level implementation handles both a
+/// `Request` query string and an `application/x-www-form-urlencoded` body.
+pub trait FromForm: Sized {
+    /// Converts a key=value pair from a urlencoded body into a type safe value.
+    fn from_form(key: &str, values: &[FormUrlDecoded]) -> Result<Self, FromQueryStringError>;
+}
+
+impl<T> FromForm for T
+    where T: FromQueryString
+{
+    fn from_form(key: &str, values: &[FormUrlDecoded]) -> Result<Self, FromQueryStringError> {
+        T::from_query_string(key, values)
+    }
+}
+
+/// Reads an `application/x-www-form-urlencoded` `Request` body into a `QueryStringMapping`,
+/// reusing the same `split` function that parses `Request` query strings.
+///
+/// Returns a descriptive error if `content_type` does not indicate a urlencoded body, or if
+/// `body` is not valid UTF-8.
+///
+/// #Examples
+///
+/// ```rust
+/// # extern crate gotham;
+/// #
+/// # use gotham::http::form::split_form_body;
+/// #
+/// # pub fn main() {
+///       let res = split_form_body(Some("application/x-www-form-urlencoded"), b"key=val&key2=val")
+///           .unwrap();
+///       assert_eq!("val", res.get("key").unwrap().first().unwrap().val());
+///
+///       assert!(split_form_body(Some("application/json"), b"key=val").is_err());
+///       assert!(split_form_body(None, b"key=val").is_err());
+/// # }
+/// ```
+pub fn split_form_body(content_type: Option<&str>, body: &[u8]) -> Result<QueryStringMapping, String> {
+    match content_type {
+        Some(content_type) if is_form_urlencoded(content_type) => {
+            let body = str::from_utf8(body)
+                .map_err(|e| format!("Unable to decode form body as UTF-8: {}", e.description()))?;
+            Ok(split(Some(body)))
+        }
+        Some(content_type) => {
+            Err(format!("Unable to extract form data, expected Content-Type \"{}\" but received \"{}\"",
+                        FORM_URLENCODED_CONTENT_TYPE,
+                        content_type))
+        }
+        None => {
+            Err(format!("Unable to extract form data, no Content-Type was supplied but \"{}\" is required",
+                        FORM_URLENCODED_CONTENT_TYPE))
+        }
+    }
+}
+
+/// Determines if a `Content-Type` header value indicates an `application/x-www-form-urlencoded`
+/// body, ignoring any trailing parameters such as a `charset`.
+fn is_form_urlencoded(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .map(|mime| mime.trim().eq_ignore_ascii_case(FORM_URLENCODED_CONTENT_TYPE))
+        .unwrap_or(false)
+}
+
+/// A `FormExtractor` that does not extract/store any data.
+///
+/// Useful in routes that don't require a urlencoded body and within documentation.
+#[derive(Debug)]
+pub struct NoopFormExtractor;
+impl FormExtractor for NoopFormExtractor {
+    fn extract(_state: &mut State, _content_type: Option<&str>, _body: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 
 use std;
 use std::error::Error;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::string::ParseError;
 use std::str::ParseBoolError;
@@ -15,6 +15,9 @@ use http::{form_url_decode, FormUrlDecoded};
 #[derive(Debug)]
 pub struct QueryStringMapping {
     data: HashMap<String, Vec<FormUrlDecoded>>,
+    tree: HashMap<String, QueryStringNode>,
+    truncated: bool,
+    present: HashSet<String>,
 }
 
 impl QueryStringMapping {
@@ -28,6 +31,132 @@ impl QueryStringMapping {
         self.data.contains_key(key)
     }
 
+    /// Indicates whether this mapping is incomplete: either `split_with_limits` stopped parsing
+    /// before reaching the end of the query string because `QueryLimits::max_params` was
+    /// reached, or `split` discarded the query string entirely because `split_with_limits`
+    /// rejected it.
+    ///
+    /// Callers that must reject oversized input outright, rather than silently act on a
+    /// partial mapping, should check this before trusting the result.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Determines if the key was given in the `Request` query string at all, whether as a
+    /// bare flag (`?verbose`), with an empty value (`?verbose=`) or with a value
+    /// (`?verbose=true`).
+    ///
+    /// Unlike `contains_key`, this is tracked independently of `data` so that
+    /// `add_unmapped_segment` - which exists for unrelated routing-segment bookkeeping and
+    /// writes into `data` for keys that were never part of the query string - cannot make a
+    /// key look present here.
+    pub fn is_present(&self, key: &str) -> bool {
+        self.present.contains(key)
+    }
+
+    /// Returns a reference to the values found at a nested path within the query string, as
+    /// produced by parsing bracketed keys such as `parent[child]`, `parent[child][grandchild]`
+    /// or `list[]` / `list[0]`.
+    ///
+    /// Returns `None` if no key reached the path, or if the path resolves to a container (a
+    /// `Map` or `List`) rather than a leaf value. A list position skipped over by an explicit
+    /// index (e.g. position `0` when only `list[2]` was given) returns an empty `Vec`.
+    ///
+    /// #Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// #
+    /// # use gotham::http::query_string::split;
+    /// #
+    /// # pub fn main() {
+    ///       let res = split(Some("user[name]=alice&user[roles][]=admin&user[roles][]=staff"));
+    ///       assert_eq!("alice", res.get_nested(&["user", "name"]).unwrap().first().unwrap().val());
+    ///       assert_eq!("admin", res.get_nested(&["user", "roles", "0"]).unwrap().first().unwrap().val());
+    ///       assert_eq!("staff", res.get_nested(&["user", "roles", "1"]).unwrap().first().unwrap().val());
+    /// # }
+    /// ```
+    pub fn get_nested(&self, path: &[&str]) -> Option<&Vec<FormUrlDecoded>> {
+        match *self.resolve_nested(path)? {
+            QueryStringNode::Leaf(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of elements in the `List` found at a nested path, as produced by
+    /// `list[]` / `list[0]` syntax.
+    ///
+    /// Lets `gotham-derive`'s recursion populate a nested `Vec` field by iterating
+    /// `0..nested_list_len(path)` rather than having to already know how many elements were
+    /// supplied. Returns `None` if the path does not resolve to a `List`.
+    ///
+    /// #Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// #
+    /// # use gotham::http::query_string::split;
+    /// #
+    /// # pub fn main() {
+    ///       let res = split(Some("user[roles][]=admin&user[roles][]=staff"));
+    ///       assert_eq!(2, res.nested_list_len(&["user", "roles"]).unwrap());
+    ///       assert!(res.nested_list_len(&["user", "name"]).is_none());
+    /// # }
+    /// ```
+    pub fn nested_list_len(&self, path: &[&str]) -> Option<usize> {
+        match *self.resolve_nested(path)? {
+            QueryStringNode::List(ref list) => Some(list.len()),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of the `Map` found at a nested path, as produced by `parent[child]`
+    /// syntax.
+    ///
+    /// Lets `gotham-derive`'s recursion populate a nested struct field by iterating its known
+    /// keys rather than having to already know which ones were supplied. Returns `None` if the
+    /// path does not resolve to a `Map`.
+    ///
+    /// #Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// #
+    /// # use gotham::http::query_string::split;
+    /// #
+    /// # pub fn main() {
+    ///       let res = split(Some("user[name]=alice&user[age]=30"));
+    ///       let mut keys = res.nested_keys(&["user"]).unwrap();
+    ///       keys.sort();
+    ///       assert_eq!(vec!["age", "name"], keys);
+    ///       assert!(res.nested_keys(&["user", "name"]).is_none());
+    /// # }
+    /// ```
+    pub fn nested_keys(&self, path: &[&str]) -> Option<Vec<&str>> {
+        match *self.resolve_nested(path)? {
+            QueryStringNode::Map(ref map) => Some(map.keys().map(String::as_str).collect()),
+            _ => None,
+        }
+    }
+
+    /// Walks `path` through `tree`, returning the `QueryStringNode` found there, whatever shape
+    /// it is. Shared by `get_nested`, `nested_list_len` and `nested_keys`, which each only
+    /// accept one particular shape.
+    fn resolve_nested(&self, path: &[&str]) -> Option<&QueryStringNode> {
+        let (root, rest) = path.split_first()?;
+        let mut node = self.tree.get(*root)?;
+
+        for segment in rest {
+            node = match *node {
+                QueryStringNode::Map(ref map) => map.get(*segment)?,
+                QueryStringNode::List(ref list) => list.get(segment.parse::<usize>().ok()?)?,
+                QueryStringNode::Leaf(_) => return None,
+            };
+        }
+
+        Some(node)
+    }
+
     /// Adds an empty value for a key, useful for keys that are considered
     /// optional and haven't been explicitly provided as part of a `Request` query string.
     pub fn add_unmapped_segment(&mut self, key: &str) {
@@ -44,6 +173,432 @@ impl QueryStringMapping {
     }
 }
 
+/// A node within the nested tree produced by parsing bracketed query string keys, such as
+/// `parent[child]` or `list[]` / `list[0]`.
+#[derive(Debug)]
+enum QueryStringNode {
+    /// A leaf holding the raw values supplied for this path.
+    Leaf(Vec<FormUrlDecoded>),
+    /// A nested map of child keys, as produced by `parent[child]` syntax.
+    Map(HashMap<String, QueryStringNode>),
+    /// An ordered list of nodes, as produced by `list[]` / `list[0]` syntax.
+    List(Vec<QueryStringNode>),
+}
+
+impl QueryStringNode {
+    /// Ensures this node is a `Map`, replacing it with an empty one if it is not already, and
+    /// returns a mutable reference to its contents.
+    ///
+    /// Errs rather than silently discarding if this node is already a non-empty `Leaf`: the
+    /// first key to reach a path decides whether it holds a scalar value or a container, and a
+    /// later, incompatible key is rejected instead of clobbering the data already recorded
+    /// there. See `QueryStringError::ConflictingShape`.
+    fn ensure_map(&mut self) -> Result<&mut HashMap<String, QueryStringNode>, QueryStringError> {
+        match *self {
+            QueryStringNode::Map(_) => {}
+            QueryStringNode::Leaf(ref values) if !values.is_empty() => {
+                return Err(QueryStringError::ConflictingShape);
+            }
+            _ => *self = QueryStringNode::Map(HashMap::new()),
+        }
+        match *self {
+            QueryStringNode::Map(ref mut map) => Ok(map),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Ensures this node is a `List`, replacing it with an empty one if it is not already, and
+    /// returns a mutable reference to its contents.
+    ///
+    /// Errs rather than silently discarding if this node is already a non-empty `Leaf`, for the
+    /// same reason as `ensure_map`.
+    fn ensure_list(&mut self) -> Result<&mut Vec<QueryStringNode>, QueryStringError> {
+        match *self {
+            QueryStringNode::List(_) => {}
+            QueryStringNode::Leaf(ref values) if !values.is_empty() => {
+                return Err(QueryStringError::ConflictingShape);
+            }
+            _ => *self = QueryStringNode::List(Vec::new()),
+        }
+        match *self {
+            QueryStringNode::List(ref mut list) => Ok(list),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Inserts `value` at the path described by `segments`, growing this node into whichever
+    /// container shape (`Map` or `List`) the next segment requires.
+    ///
+    /// The first key to reach a given path decides its shape, scalar (`Leaf`) or container
+    /// (`Map`/`List`); a later key for the same path that disagrees (e.g. `user=foo` together
+    /// with `user[name]=bar`, in either order) is rejected with
+    /// `QueryStringError::ConflictingShape` rather than silently discarding whichever value
+    /// arrived first.
+    ///
+    /// Rejects an `Index` segment whose value exceeds `limits.max_list_index` rather than
+    /// pre-filling the list up to that index, since the index comes straight from the key and
+    /// is cheap to check before any allocation happens.
+    fn insert(&mut self,
+              segments: &[KeySegment],
+              value: FormUrlDecoded,
+              limits: &QueryLimits)
+              -> Result<(), QueryStringError> {
+        match segments.split_first() {
+            None => {
+                match *self {
+                    QueryStringNode::Leaf(ref mut values) => values.push(value),
+                    _ => return Err(QueryStringError::ConflictingShape),
+                }
+            }
+            Some((&KeySegment::Name(ref name), rest)) => {
+                self.ensure_map()?
+                    .entry(name.clone())
+                    .or_insert_with(|| QueryStringNode::Leaf(Vec::new()))
+                    .insert(rest, value, limits)?;
+            }
+            Some((&KeySegment::Append, rest)) => {
+                let mut child = QueryStringNode::Leaf(Vec::new());
+                child.insert(rest, value, limits)?;
+                self.ensure_list()?.push(child);
+            }
+            Some((&KeySegment::Index(index), rest)) => {
+                if index > limits.max_list_index {
+                    return Err(QueryStringError::IndexTooLarge {
+                                   index,
+                                   limit: limits.max_list_index,
+                               });
+                }
+
+                let list = self.ensure_list()?;
+                while list.len() <= index {
+                    list.push(QueryStringNode::Leaf(Vec::new()));
+                }
+                list[index].insert(rest, value, limits)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single segment of a bracket-parsed query string key, e.g. `parent[child][0]` parses to
+/// `[Name("parent"), Name("child"), Index(0)]`.
+#[derive(Debug, Clone, PartialEq)]
+enum KeySegment {
+    /// A plain or bracketed name, e.g. `parent` or `[child]`.
+    Name(String),
+    /// An explicit numeric index into a list, e.g. `[0]`.
+    Index(usize),
+    /// An empty bracket pair appending to a list, e.g. `[]`.
+    Append,
+}
+
+/// Parses a decoded query string key into the segments implied by its `parent[child]`,
+/// `parent[child][grandchild]` and `list[]` / `list[0]` bracket syntax.
+///
+/// Keys with unbalanced or otherwise malformed brackets are not rejected; they degrade to a
+/// single literal segment so that a key such as `a[` is treated as the literal key `a[` rather
+/// than causing a panic.
+fn parse_key_segments(raw: &str) -> Vec<KeySegment> {
+    if !raw.contains('[') {
+        return vec![KeySegment::Name(raw.to_string())];
+    }
+
+    let root_end = raw.find('[').unwrap();
+    let mut segments = vec![KeySegment::Name(raw[..root_end].to_string())];
+    let mut rest = &raw[root_end..];
+
+    while !rest.is_empty() {
+        if !rest.starts_with('[') {
+            return vec![KeySegment::Name(raw.to_string())];
+        }
+
+        let close = match rest.find(']') {
+            Some(index) => index,
+            None => return vec![KeySegment::Name(raw.to_string())],
+        };
+
+        let inner = &rest[1..close];
+        if inner.is_empty() {
+            segments.push(KeySegment::Append);
+        } else if let Ok(index) = inner.parse::<usize>() {
+            segments.push(KeySegment::Index(index));
+        } else {
+            segments.push(KeySegment::Name(inner.to_string()));
+        }
+
+        rest = &rest[close + 1..];
+    }
+
+    segments
+}
+
+/// Configurable limits applied by `split_with_limits` to bound the memory a maliciously crafted
+/// `Request` query string can make it allocate.
+///
+/// `Default` provides sensible values for an application that has no specific requirements of
+/// its own.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryLimits {
+    /// The maximum number of `key=value` pairs, including bare flags, that will be parsed.
+    pub max_params: usize,
+    /// The maximum length, in bytes, of a single still percent-encoded key.
+    pub max_key_len: usize,
+    /// The maximum length, in bytes, of a single still percent-encoded value.
+    pub max_value_len: usize,
+    /// The maximum length, in bytes, of the query string as a whole.
+    pub max_total_len: usize,
+    /// The maximum numeric index accepted in a bracketed list key such as `list[0]`.
+    ///
+    /// Without this, a tiny key like `list[50000000]` would otherwise make
+    /// `QueryStringNode::insert` pre-fill tens of millions of empty list entries to reach the
+    /// requested index, the same unbounded allocation `QueryLimits` exists to prevent.
+    pub max_list_index: usize,
+    /// The maximum number of bracket-nesting levels accepted in a single key, e.g. `a[b][c][d]`
+    /// nests 4 deep.
+    ///
+    /// `QueryStringNode::insert` recurses once per level, so without a cap independent of
+    /// `max_key_len` an application that raises `max_key_len` (a reasonable thing to do, e.g. to
+    /// allow longer values) would get deep recursive `insert` calls for free, with no
+    /// corresponding safeguard against overflowing the stack.
+    pub max_bracket_depth: usize,
+}
+
+impl Default for QueryLimits {
+    fn default() -> QueryLimits {
+        QueryLimits {
+            max_params: 1_000,
+            max_key_len: 1_024,
+            max_value_len: 8_192,
+            max_total_len: 64 * 1024,
+            max_list_index: 1_000,
+            max_bracket_depth: 32,
+        }
+    }
+}
+
+/// Represents a `Request` query string that could not be parsed because it exceeded the
+/// `QueryLimits` supplied to `split_with_limits`.
+#[derive(Debug)]
+pub enum QueryStringError {
+    /// The query string itself exceeded `QueryLimits::max_total_len`.
+    TooLong {
+        /// The configured limit that was exceeded.
+        limit: usize,
+        /// The actual length of the offending query string.
+        actual: usize,
+    },
+    /// A single key exceeded `QueryLimits::max_key_len`.
+    KeyTooLong {
+        /// The length of the offending key.
+        key_len: usize,
+        /// The configured limit that was exceeded.
+        limit: usize,
+    },
+    /// A single value exceeded `QueryLimits::max_value_len`.
+    ValueTooLong {
+        /// The length of the offending value.
+        value_len: usize,
+        /// The configured limit that was exceeded.
+        limit: usize,
+    },
+    /// A bracketed list index, e.g. the `50000000` in `list[50000000]`, exceeded
+    /// `QueryLimits::max_list_index`.
+    IndexTooLarge {
+        /// The offending index.
+        index: usize,
+        /// The configured limit that was exceeded.
+        limit: usize,
+    },
+    /// A key's bracket nesting, e.g. the four levels in `a[b][c][d]`, exceeded
+    /// `QueryLimits::max_bracket_depth`.
+    NestingTooDeep {
+        /// The nesting depth of the offending key.
+        depth: usize,
+        /// The configured limit that was exceeded.
+        limit: usize,
+    },
+    /// A path was used with two incompatible shapes, e.g. `user=foo` together with
+    /// `user[name]=bar`. The key that reaches a path first decides whether it holds a scalar
+    /// value or a container; a later key that disagrees is rejected rather than silently
+    /// discarding whichever value arrived first.
+    ConflictingShape,
+}
+
+impl std::fmt::Display for QueryStringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Error decoding query string: {}", self.description())
+    }
+}
+
+impl Error for QueryStringError {
+    fn description(&self) -> &str {
+        match *self {
+            QueryStringError::TooLong { .. } => {
+                "query string exceeded the configured maximum total length"
+            }
+            QueryStringError::KeyTooLong { .. } => {
+                "a query string key exceeded the configured maximum length"
+            }
+            QueryStringError::ValueTooLong { .. } => {
+                "a query string value exceeded the configured maximum length"
+            }
+            QueryStringError::IndexTooLarge { .. } => {
+                "a query string list index exceeded the configured maximum"
+            }
+            QueryStringError::NestingTooDeep { .. } => {
+                "a query string key's bracket nesting exceeded the configured maximum depth"
+            }
+            QueryStringError::ConflictingShape => {
+                "a query string key was used with two incompatible shapes"
+            }
+        }
+    }
+}
+
+/// Splits a query string into pairs and provides a mapping of keys to values, the same way
+/// `split` does, but enforces `limits` along the way.
+///
+/// The query string as a whole, and any individual key or value, exceeding its configured limit
+/// is treated as a hard error since it can be detected before any unbounded allocation happens.
+/// Exceeding `QueryLimits::max_params` instead stops parsing early and returns a mapping with
+/// `QueryStringMapping::is_truncated() == true`, since everything parsed up to that point is
+/// still valid; callers that must reject oversized input outright, rather than silently act on
+/// a partial mapping, should check `is_truncated` themselves.
+///
+/// #Examples
+///
+/// ```rust
+/// # extern crate gotham;
+/// #
+/// # use gotham::http::query_string::{split_with_limits, QueryLimits};
+/// #
+/// # pub fn main() {
+///       let limits = QueryLimits { max_params: 1, ..QueryLimits::default() };
+///       let res = split_with_limits(Some("a=1&b=2"), &limits).unwrap();
+///       assert!(res.is_truncated());
+///       assert!(res.contains_key("a"));
+///       assert!(!res.contains_key("b"));
+///
+///       let limits = QueryLimits { max_total_len: 4, ..QueryLimits::default() };
+///       assert!(split_with_limits(Some("key=value"), &limits).is_err());
+///
+///       // A tiny key can still request a huge list index; that is bounded by
+///       // `max_list_index` rather than the length based limits above.
+///       assert!(split_with_limits(Some("list[50000000]=x"), &QueryLimits::default()).is_err());
+///
+///       // A tiny key can also nest arbitrarily deep; that is bounded by `max_bracket_depth`.
+///       let deeply_nested = "a".to_string() + &"[a]".repeat(100) + "=x";
+///       assert!(split_with_limits(Some(&deeply_nested), &QueryLimits::default()).is_err());
+///
+///       // A path used with two incompatible shapes is rejected rather than silently
+///       // discarding whichever value arrived first.
+///       assert!(split_with_limits(Some("user=foo&user[name]=bar"), &QueryLimits::default())
+///                   .is_err());
+/// # }
+/// ```
+pub fn split_with_limits<'r>(query: Option<&'r str>,
+                              limits: &QueryLimits)
+                              -> Result<QueryStringMapping, QueryStringError> {
+    let query = match query {
+        Some(query) => query,
+        None => {
+            return Ok(QueryStringMapping {
+                          data: HashMap::new(),
+                          tree: HashMap::new(),
+                          truncated: false,
+                          present: HashSet::new(),
+                      })
+        }
+    };
+
+    if query.len() > limits.max_total_len {
+        return Err(QueryStringError::TooLong {
+                       limit: limits.max_total_len,
+                       actual: query.len(),
+                   });
+    }
+
+    let mut data = HashMap::new();
+    let mut tree: HashMap<String, QueryStringNode> = HashMap::new();
+    let mut present = HashSet::new();
+    let mut truncated = false;
+    let mut params = 0;
+
+    for p in query.split("&").filter(|pair| !pair.is_empty()) {
+        if params >= limits.max_params {
+            truncated = true;
+            break;
+        }
+
+        if !p.contains("=") {
+            if p.len() > limits.max_key_len {
+                return Err(QueryStringError::KeyTooLong {
+                               key_len: p.len(),
+                               limit: limits.max_key_len,
+                           });
+            }
+            if let Ok(k) = form_url_decode(p) {
+                data.entry(k.clone()).or_insert_with(Vec::new);
+                present.insert(k);
+                params += 1;
+            }
+            continue;
+        }
+
+        let mut sp = p.split("=");
+        let (k, v) = (sp.next().unwrap(), sp.next().unwrap());
+
+        if k.len() > limits.max_key_len {
+            return Err(QueryStringError::KeyTooLong {
+                           key_len: k.len(),
+                           limit: limits.max_key_len,
+                       });
+        }
+        if v.len() > limits.max_value_len {
+            return Err(QueryStringError::ValueTooLong {
+                           value_len: v.len(),
+                           limit: limits.max_value_len,
+                       });
+        }
+
+        let k = match form_url_decode(k) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+        let dv = match FormUrlDecoded::new(v) {
+            Some(dv) => dv,
+            None => continue,
+        };
+
+        let segments = parse_key_segments(&k);
+        if segments.len() > limits.max_bracket_depth {
+            return Err(QueryStringError::NestingTooDeep {
+                           depth: segments.len(),
+                           limit: limits.max_bracket_depth,
+                       });
+        }
+
+        if let Some((KeySegment::Name(name), rest)) = segments.split_first() {
+            tree.entry(name.clone())
+                .or_insert_with(|| QueryStringNode::Leaf(Vec::new()))
+                .insert(rest, dv.clone(), limits)?;
+        }
+
+        present.insert(k.clone());
+        let vec = data.entry(k).or_insert(Vec::new());
+        vec.push(dv);
+        params += 1;
+    }
+
+    Ok(QueryStringMapping {
+           data,
+           tree,
+           truncated,
+           present,
+       })
+}
+
 /// Splits a query string into pairs and provides a mapping of keys to values.
 ///
 /// For keys which are represented 1..n times in the query string the resultant Vec will be
@@ -51,6 +606,17 @@ impl QueryStringMapping {
 ///
 /// For keys that are provided but don't have a value associated an empty String will be stored.
 ///
+/// For keys that are provided as a bare flag, without an `=` at all (e.g. `?verbose`), an entry
+/// is still recorded so that `is_present` can report the key as given, but no value is added to
+/// its Vec.
+///
+/// A thin wrapper around `split_with_limits` using `QueryLimits::default()`; a query string
+/// that `split_with_limits` rejects with a `QueryStringError` degrades to an empty,
+/// `is_truncated() == true` mapping rather than panicking or returning a `Result`, so existing
+/// call sites built around this signature keep working. A query string with more than
+/// `max_params` pairs keeps its first `max_params` pairs and is also `is_truncated() == true`. Use `split_with_limits` directly if application specific limits, or access to
+/// the precise `QueryStringError`, are needed.
+///
 /// #Examples
 ///
 /// ```rust
@@ -70,32 +636,29 @@ impl QueryStringMapping {
 ///       let res = split(Some("key=val&key2="));
 ///       assert_eq!("val", res.get("key").unwrap().first().unwrap().val());
 ///       assert_eq!("", res.get("key2").unwrap().first().unwrap().val());
+///
+///       let res = split(Some("verbose&limit=10"));
+///       assert!(res.is_present("verbose"));
+///       assert!(res.get("verbose").unwrap().is_empty());
+///       assert!(!res.is_present("missing"));
+///
+///       // `add_unmapped_segment` is unrelated routing-segment bookkeeping; it must not make
+///       // a key that was never in the query string look present.
+///       let mut res = split(Some("limit=10"));
+///       res.add_unmapped_segment("verbose");
+///       assert!(res.contains_key("verbose"));
+///       assert!(!res.is_present("verbose"));
 /// # }
 /// ```
 pub fn split<'r>(query: Option<&'r str>) -> QueryStringMapping {
-    match query {
-        Some(query) => {
-            let pairs = query.split("&").filter(|pair| pair.contains("="));
-            let data = pairs.fold(HashMap::new(), |mut acc, p| {
-                let mut sp = p.split("=");
-                let (k, v) = (sp.next().unwrap(), sp.next().unwrap());
-                match form_url_decode(k) {
-                    Ok(k) => {
-                        let vec = acc.entry(k).or_insert(Vec::new());
-                        match FormUrlDecoded::new(v) {
-                            Some(dv) => vec.push(dv),
-                            None => (),
-                        }
-                    }
-                    Err(_) => (),
-                };
-                acc
-            });
-
-            QueryStringMapping { data }
-        }
-        None => QueryStringMapping { data: HashMap::new() },
-    }
+    split_with_limits(query, &QueryLimits::default()).unwrap_or_else(|_| {
+        QueryStringMapping {
+            data: HashMap::new(),
+            tree: HashMap::new(),
+            truncated: true,
+            present: HashSet::new(),
+        }
+    })
 }
 
 /// Derived through the macro of the same name supplied by `gotham-derive` for application defined
@@ -126,6 +689,15 @@ pub struct FromQueryStringError {
     description: String,
 }
 
+impl FromQueryStringError {
+    /// Creates a new `FromQueryStringError` with a custom description, for use by callers
+    /// outside this module that need to report a query string error of their own (e.g. a
+    /// malformed `FilterExpr`).
+    pub fn new<S: Into<String>>(description: S) -> FromQueryStringError {
+        FromQueryStringError { description: description.into() }
+    }
+}
+
 impl std::fmt::Display for FromQueryStringError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "Error decoding query string: {}", self.description)
@@ -166,6 +738,34 @@ impl<T> FromQueryString for Option<T>
     }
 }
 
+/// Converts nested data received as part of a `Request` query string into type safe values,
+/// recursing through `parent[child]` and `list[]` paths the way `FromQueryString` converts a
+/// single flat key.
+///
+/// Derived through the macro of the same name supplied by `gotham-derive` for struct fields
+/// whose type itself implements `FromQueryTree`, allowing nested structs and collections to be
+/// extracted from a `QueryStringMapping` built by `split`.
+pub trait FromQueryTree {
+    /// Converts the values found at `path` within `mapping` into a type safe value.
+    fn from_query_tree(path: &[&str],
+                        mapping: &QueryStringMapping)
+                        -> Result<Self, FromQueryStringError>
+        where Self: Sized;
+}
+
+impl<T> FromQueryTree for T
+    where T: FromQueryString
+{
+    fn from_query_tree(path: &[&str],
+                        mapping: &QueryStringMapping)
+                        -> Result<Self, FromQueryStringError> {
+        let key = path.last().cloned().unwrap_or("");
+        let empty = Vec::new();
+        let values = mapping.get_nested(path).unwrap_or(&empty);
+        T::from_query_string(key, values)
+    }
+}
+
 impl<T> FromQueryString for Vec<T>
     where T: FromQueryString
 {
@@ -221,7 +821,6 @@ macro_rules! fstr {
 }
 
 fstr!(String,
-      bool,
       f32,
       f64,
       isize,
@@ -233,4 +832,53 @@ fstr!(String,
       u8,
       u16,
       u32,
-      u64);
\ No newline at end of file
+      u64,
+      bool);
+
+/// A boolean flag extracted directly from a `QueryStringMapping` rather than through
+/// `FromQueryString`, so that it can tell "key absent" (`false`) apart from "key present without
+/// a value" (`true`, e.g. the `verbose` in `?verbose&limit=10`).
+///
+/// `FromQueryString::from_query_string` only ever sees `values`, the same slice regardless of
+/// whether the key was missing entirely or present with zero values (that's why the `Option<T>`
+/// impl above has to treat `values.len() == 0` as `None` either way) - there is no way for a
+/// plain `bool` field to make that distinction safely. A `bool` field that is never mentioned in
+/// the query string would otherwise silently extract as `true`, a dangerous default for
+/// something like an `admin`/`force` flag. Use `Flag` instead wherever that distinction matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag(pub bool);
+
+impl Flag {
+    /// Extracts a `Flag` for `key` from `mapping`: `false` if `key` was never given,
+    /// `true` if it was given without a value, or the explicitly supplied boolean otherwise.
+    ///
+    /// #Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// #
+    /// # use gotham::http::query_string::{split, Flag};
+    /// #
+    /// # pub fn main() {
+    ///       let res = split(Some("verbose&explicit=false"));
+    ///       assert_eq!(Flag(true), Flag::extract("verbose", &res).unwrap());
+    ///       assert_eq!(Flag(false), Flag::extract("explicit", &res).unwrap());
+    ///       assert_eq!(Flag(false), Flag::extract("missing", &res).unwrap());
+    /// # }
+    /// ```
+    pub fn extract(key: &str, mapping: &QueryStringMapping) -> Result<Flag, FromQueryStringError> {
+        if !mapping.is_present(key) {
+            return Ok(Flag(false));
+        }
+        match mapping.get(key) {
+            None => Ok(Flag(false)),
+            Some(values) if values.is_empty() => Ok(Flag(true)),
+            Some(values) if values.len() == 1 => Ok(Flag(bool::from_str(values[0].val())?)),
+            Some(_) => {
+                Err(FromQueryStringError {
+                        description: String::from("Invalid number of values")
+                    })
+            }
+        }
+    }
+}
\ No newline at end of file